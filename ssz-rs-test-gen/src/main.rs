@@ -2,12 +2,15 @@
 //! Not user facing.
 #![doc(hidden)]
 
+mod schema;
+
 use convert_case::{Case, Casing};
 use num_bigint::BigUint;
 use std::{collections::BTreeMap, env, ffi::OsStr, fmt, fs, fs::DirEntry, path::PathBuf};
 
 const DRY_RUN: bool = false;
 const SRC_DIR: &str = "consensus-spec-tests/tests/general/phase0/ssz_generic/";
+const STATIC_SRC_DIR: &str = "consensus-spec-tests/tests/general/";
 const TARGET_DIR: &str = "../ssz-rs/tests/";
 
 const SRC_PREAMBLE: &str = r#"//! This file was generated by `ssz-rs-test-gen`; do NOT manually edit.
@@ -66,7 +69,7 @@ struct BitsStruct {
 }
 "#;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 enum SszType {
     BasicVector,
     Bitlist,
@@ -74,6 +77,11 @@ enum SszType {
     Boolean,
     Container,
     Uint,
+    /// A container from one of the `ssz_static` suites, e.g. `{ fork: "altair",
+    /// type_name: "SyncAggregate" }`. Unlike the other variants, the concrete Rust
+    /// type isn't derived from the test case name -- it's the fork's schema entry for
+    /// `type_name`, shared by every case under that fork/type directory.
+    Static { fork: String, type_name: String },
 }
 
 impl From<&str> for SszType {
@@ -99,6 +107,29 @@ impl fmt::Display for SszType {
             Self::Boolean => write!(f, "boolean"),
             Self::Container => write!(f, "containers"),
             Self::Uint => write!(f, "uints"),
+            Self::Static { fork, type_name } => write!(f, "{fork}/{}", type_name.to_case(Case::Snake)),
+        }
+    }
+}
+
+impl SszType {
+    /// The Rust type a test case's value should be parsed into. For every suite but
+    /// `ssz_static` this is derived from the test case's own name (e.g.
+    /// `vec_bool_1` -> `Vector<bool, 1>`); `ssz_static` containers instead carry their
+    /// type name directly, since every case under a fork/type directory shares it.
+    fn to_rust_type(&self, name: &str) -> String {
+        match self {
+            Self::Static { type_name, .. } => type_name.clone(),
+            other => to_rust_type(other, name),
+        }
+    }
+
+    /// Identifier-safe prefix for the generated test functions, distinct from
+    /// `Display` (which instead yields the output file's directory/name).
+    fn test_prefix(&self) -> String {
+        match self {
+            Self::Static { fork, type_name } => schema::test_prefix(fork, type_name),
+            other => other.to_string(),
         }
     }
 }
@@ -323,8 +354,9 @@ fn to_element_type(s: &str) -> String {
     }
 }
 
-fn to_rust_type(ssz_type: SszType, name: &str) -> String {
+fn to_rust_type(ssz_type: &SszType, name: &str) -> String {
     match ssz_type {
+        SszType::Static { .. } => unreachable!("handled by SszType::to_rust_type"),
         SszType::BasicVector => {
             let parts = name.split('_').collect::<Vec<&str>>();
             let element_type = to_element_type(parts[1]);
@@ -374,6 +406,12 @@ struct TestCase {
 #[derive(Debug)]
 struct Generator {
     ssz_type: SszType,
+    /// Root of the spec test tree this generator's `data_path`s were read from, so
+    /// `execute` can compute each case's path relative to the project's `tests/data`.
+    src_base: PathBuf,
+    /// The fork's container table, for `ssz_static` generators; `None` for every
+    /// other suite, which resolves Rust types/values from the test case name instead.
+    schema: Option<schema::ForkSchema>,
     components: Vec<String>,
     test_cases: BTreeMap<String, TestCase>,
 }
@@ -381,10 +419,19 @@ struct Generator {
 impl Generator {
     fn new(ssz_type: SszType) -> Self {
         let mut components = vec![SRC_PREAMBLE.to_string()];
-        if matches!(ssz_type, SszType::Container) {
-            components.push(CONTAINERS_DEFN_FMT.to_string());
-        }
-        Self { ssz_type, components, test_cases: Default::default() }
+        let (schema, src_base) = match &ssz_type {
+            SszType::Container => {
+                components.push(CONTAINERS_DEFN_FMT.to_string());
+                (None, PathBuf::from(SRC_DIR))
+            }
+            SszType::Static { fork, .. } => {
+                let fork_schema = schema::schema_for_fork(fork);
+                components.push(schema::compile_schema(fork_schema));
+                (Some(fork_schema), PathBuf::from(STATIC_SRC_DIR))
+            }
+            _ => (None, PathBuf::from(SRC_DIR)),
+        };
+        Self { ssz_type, src_base, schema, components, test_cases: Default::default() }
     }
 
     fn load_test_case(&mut self, format: Format, path: DirEntry) {
@@ -417,9 +464,13 @@ impl Generator {
 
     fn execute(self) {
         let target_dir = PathBuf::from(TARGET_DIR);
-        let ssz_type = self.ssz_type.to_string();
-        let mut target_file_path = target_dir.join(&ssz_type);
+        let target_path = self.ssz_type.to_string();
+        let test_prefix = self.ssz_type.test_prefix();
+        let mut target_file_path = target_dir.join(&target_path);
         target_file_path.set_extension("rs");
+        if let Some(parent) = target_file_path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
         let mut target_file = do_create(target_file_path);
         for component in self.components {
             do_write(&mut target_file, component);
@@ -427,21 +478,24 @@ impl Generator {
         for (name, test_case) in self.test_cases {
             let src_data_path = test_case.data_path.unwrap();
             let target_data_path =
-                target_dir.join("data").join(src_data_path.strip_prefix(SRC_DIR).unwrap());
+                target_dir.join("data").join(src_data_path.strip_prefix(&self.src_base).unwrap());
             do_copy(&src_data_path, &target_data_path);
 
-            let rust_type = to_rust_type(self.ssz_type, &name);
+            let rust_type = self.ssz_type.to_rust_type(&name);
             let project_path = target_data_path.strip_prefix("..").unwrap();
             let target_data_path = project_path.display();
             match test_case.format {
                 Format::Valid => {
-                    let value = to_rust_value(&name, &rust_type, test_case.value.unwrap());
+                    let value = match self.schema {
+                        Some(schema) => schema::to_rust_value(schema, &rust_type, test_case.value.unwrap()),
+                        None => to_rust_value(&name, &rust_type, test_case.value.unwrap()),
+                    };
                     let root = test_case.root.unwrap();
                     let name = name.to_case(Case::Snake);
                     let source = format!(
                         r#"
                 #[test]
-                fn test_{ssz_type}_{name}() {{
+                fn test_{test_prefix}_{name}() {{
                     let value = {value};
                     let encoding = serialize(&value);
                     let expected_encoding = read_ssz_snappy_from_test_data("{target_data_path}");
@@ -464,7 +518,7 @@ impl Generator {
                         r#"
                 #[test]
                 #[should_panic]
-                fn test_{ssz_type}_{name}() {{
+                fn test_{test_prefix}_{name}() {{
                     let encoding = read_ssz_snappy_from_test_data("{target_data_path}");
 
                     deserialize::<{rust_type}>(&encoding);
@@ -479,9 +533,11 @@ impl Generator {
 }
 
 fn generate_for(ssz_type: SszType) {
-    let fmt = Format::Valid;
+    let dir_name = ssz_type.to_string();
     let mut generator = Generator::new(ssz_type);
-    let test_suite_path = PathBuf::from(SRC_DIR).join(ssz_type.to_string()).join(fmt.to_string());
+
+    let fmt = Format::Valid;
+    let test_suite_path = PathBuf::from(SRC_DIR).join(&dir_name).join(fmt.to_string());
     for test_case in fs::read_dir(test_suite_path).unwrap() {
         match test_case {
             Ok(path) => generator.load_test_case(fmt, path),
@@ -490,7 +546,7 @@ fn generate_for(ssz_type: SszType) {
     }
 
     let fmt = Format::Invalid;
-    let test_suite_path = PathBuf::from(SRC_DIR).join(ssz_type.to_string()).join(fmt.to_string());
+    let test_suite_path = PathBuf::from(SRC_DIR).join(&dir_name).join(fmt.to_string());
     for test_case in fs::read_dir(test_suite_path).unwrap() {
         match test_case {
             Ok(path) => generator.load_test_case(fmt, path),
@@ -500,6 +556,34 @@ fn generate_for(ssz_type: SszType) {
     generator.execute();
 }
 
+/// Generate tests for one `ssz_static` container in one fork, reading every
+/// `ssz_random` case directory under it (there is no valid/invalid split for this
+/// suite -- every case is a real, validly-encoded object).
+fn generate_static_for(fork: &str, type_name: &str) {
+    let ssz_type = SszType::Static { fork: fork.to_string(), type_name: type_name.to_string() };
+    let mut generator = Generator::new(ssz_type);
+
+    let case_dir =
+        PathBuf::from(STATIC_SRC_DIR).join(fork).join("ssz_static").join(type_name).join("ssz_random");
+    for test_case in fs::read_dir(case_dir).unwrap() {
+        match test_case {
+            Ok(path) => generator.load_test_case(Format::Valid, path),
+            Err(err) => panic!("{err}"),
+        };
+    }
+    generator.execute();
+}
+
+/// Walk every fork's schema and generate the `ssz_static` tests for each container it
+/// describes, rather than requiring a fork/type name per invocation.
+fn generate_static_for_all_forks() {
+    for &fork in schema::FORKS {
+        for &(type_name, _) in schema::schema_for_fork(fork) {
+            generate_static_for(fork, type_name);
+        }
+    }
+}
+
 fn main() {
     let current_dir = env::current_dir().unwrap();
     let current_dir = current_dir.file_name().unwrap();
@@ -507,10 +591,9 @@ fn main() {
         panic!("please call this utility from the `ssz-rs-test-gen` package");
     }
 
-    if let Some(ssz_type) = env::args().nth(1) {
-        let ssz_type = SszType::from(ssz_type.as_ref());
-        generate_for(ssz_type);
-    } else {
-        panic!("please supply a SSZ type from the spec tests to proceed")
+    match env::args().nth(1).as_deref() {
+        Some("ssz_static") => generate_static_for_all_forks(),
+        Some(ssz_type) => generate_for(SszType::from(ssz_type)),
+        None => panic!("please supply a SSZ type from the spec tests to proceed"),
     }
 }
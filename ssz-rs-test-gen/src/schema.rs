@@ -0,0 +1,391 @@
+//! Per-fork schema tables for the `ssz_static` spec test suites.
+//!
+//! The `ssz_generic` suite's containers (`CONTAINERS_DEFN_FMT` in `main.rs`) are few
+//! and stable, so hand-transcribing them once was fine. `ssz_static` is the opposite:
+//! `BeaconBlock`, `BeaconState`, `Attestation` and the rest change shape from fork to
+//! fork and have far too many fields to keep in sync by hand on every spec bump.
+//! Instead each fork's relevant containers are described declaratively as a
+//! field-name -> [`FieldType`] table below, and [`compile_schema`] turns a table into
+//! the same kind of `#[derive(SimpleSerialize)] struct { .. }` source
+//! `CONTAINERS_DEFN_FMT` writes out by hand, while [`to_rust_value`] resolves a
+//! `value.yaml` mapping into a Rust value expression the same way, recursing into
+//! nested containers and `List`/`Vector`-of-container fields via the table instead of
+//! a fixed set of per-type-name match arms.
+
+use convert_case::{Case, Casing};
+
+/// Forks with an `ssz_static` schema below, in spec release order.
+pub const FORKS: &[&str] = &["phase0", "altair", "bellatrix", "capella", "deneb"];
+
+/// One field's SSZ type, as it should appear in a generated Rust struct.
+#[derive(Debug, Clone, Copy)]
+pub enum FieldType {
+    /// A basic type, or an already-resolved `Vector`/`List`/`Bitlist`/`Bitvector`
+    /// spelling, e.g. `"u64"` or `"List<u8, 1073741824>"`.
+    Basic(&'static str),
+    /// A reference to another container defined in the same fork's schema.
+    Container(&'static str),
+    /// A homogeneous list of containers, bounded by `limit`.
+    ContainerList { element: &'static str, limit: usize },
+    /// A homogeneous vector of containers, of length `length`.
+    ContainerVector { element: &'static str, length: usize },
+}
+
+/// The fields of one container, in declaration order (order is significant for SSZ
+/// encoding, so it must match the spec's field order, not an alphabetized one).
+pub type ContainerSchema = &'static [(&'static str, FieldType)];
+
+/// Every container known for one fork, keyed by type name.
+pub type ForkSchema = &'static [(&'static str, ContainerSchema)];
+
+const CHECKPOINT: ContainerSchema =
+    &[("epoch", FieldType::Basic("u64")), ("root", FieldType::Basic("Vector<u8, 32>"))];
+
+const ETH1_DATA: ContainerSchema = &[
+    ("deposit_root", FieldType::Basic("Vector<u8, 32>")),
+    ("deposit_count", FieldType::Basic("u64")),
+    ("block_hash", FieldType::Basic("Vector<u8, 32>")),
+];
+
+const BEACON_BLOCK_HEADER: ContainerSchema = &[
+    ("slot", FieldType::Basic("u64")),
+    ("proposer_index", FieldType::Basic("u64")),
+    ("parent_root", FieldType::Basic("Vector<u8, 32>")),
+    ("state_root", FieldType::Basic("Vector<u8, 32>")),
+    ("body_root", FieldType::Basic("Vector<u8, 32>")),
+];
+
+const ATTESTATION_DATA: ContainerSchema = &[
+    ("slot", FieldType::Basic("u64")),
+    ("index", FieldType::Basic("u64")),
+    ("beacon_block_root", FieldType::Basic("Vector<u8, 32>")),
+    ("source", FieldType::Container("Checkpoint")),
+    ("target", FieldType::Container("Checkpoint")),
+];
+
+const ATTESTATION_PHASE0: ContainerSchema = &[
+    ("aggregation_bits", FieldType::Basic("Bitlist<2048>")),
+    ("data", FieldType::Container("AttestationData")),
+    ("signature", FieldType::Basic("Vector<u8, 96>")),
+];
+
+const FORK: ContainerSchema = &[
+    ("previous_version", FieldType::Basic("Vector<u8, 4>")),
+    ("current_version", FieldType::Basic("Vector<u8, 4>")),
+    ("epoch", FieldType::Basic("u64")),
+];
+
+const VALIDATOR: ContainerSchema = &[
+    ("pubkey", FieldType::Basic("Vector<u8, 48>")),
+    ("withdrawal_credentials", FieldType::Basic("Vector<u8, 32>")),
+    ("effective_balance", FieldType::Basic("u64")),
+    ("slashed", FieldType::Basic("bool")),
+    ("activation_eligibility_epoch", FieldType::Basic("u64")),
+    ("activation_epoch", FieldType::Basic("u64")),
+    ("exit_epoch", FieldType::Basic("u64")),
+    ("withdrawable_epoch", FieldType::Basic("u64")),
+];
+
+const PENDING_ATTESTATION: ContainerSchema = &[
+    ("aggregation_bits", FieldType::Basic("Bitlist<2048>")),
+    ("data", FieldType::Container("AttestationData")),
+    ("inclusion_delay", FieldType::Basic("u64")),
+    ("proposer_index", FieldType::Basic("u64")),
+];
+
+const INDEXED_ATTESTATION: ContainerSchema = &[
+    ("attesting_indices", FieldType::Basic("List<u64, 2048>")),
+    ("data", FieldType::Container("AttestationData")),
+    ("signature", FieldType::Basic("Vector<u8, 96>")),
+];
+
+const ATTESTER_SLASHING: ContainerSchema = &[
+    ("attestation_1", FieldType::Container("IndexedAttestation")),
+    ("attestation_2", FieldType::Container("IndexedAttestation")),
+];
+
+const SIGNED_BEACON_BLOCK_HEADER: ContainerSchema = &[
+    ("message", FieldType::Container("BeaconBlockHeader")),
+    ("signature", FieldType::Basic("Vector<u8, 96>")),
+];
+
+const PROPOSER_SLASHING: ContainerSchema = &[
+    ("signed_header_1", FieldType::Container("SignedBeaconBlockHeader")),
+    ("signed_header_2", FieldType::Container("SignedBeaconBlockHeader")),
+];
+
+const DEPOSIT_DATA: ContainerSchema = &[
+    ("pubkey", FieldType::Basic("Vector<u8, 48>")),
+    ("withdrawal_credentials", FieldType::Basic("Vector<u8, 32>")),
+    ("amount", FieldType::Basic("u64")),
+    ("signature", FieldType::Basic("Vector<u8, 96>")),
+];
+
+const DEPOSIT: ContainerSchema = &[
+    ("proof", FieldType::Basic("Vector<Vector<u8, 32>, 33>")),
+    ("data", FieldType::Container("DepositData")),
+];
+
+const VOLUNTARY_EXIT: ContainerSchema =
+    &[("epoch", FieldType::Basic("u64")), ("validator_index", FieldType::Basic("u64"))];
+
+const SIGNED_VOLUNTARY_EXIT: ContainerSchema = &[
+    ("message", FieldType::Container("VoluntaryExit")),
+    ("signature", FieldType::Basic("Vector<u8, 96>")),
+];
+
+const BEACON_BLOCK_BODY: ContainerSchema = &[
+    ("randao_reveal", FieldType::Basic("Vector<u8, 96>")),
+    ("eth1_data", FieldType::Container("Eth1Data")),
+    ("graffiti", FieldType::Basic("Vector<u8, 32>")),
+    ("proposer_slashings", FieldType::ContainerList { element: "ProposerSlashing", limit: 16 }),
+    ("attester_slashings", FieldType::ContainerList { element: "AttesterSlashing", limit: 2 }),
+    ("attestations", FieldType::ContainerList { element: "Attestation", limit: 128 }),
+    ("deposits", FieldType::ContainerList { element: "Deposit", limit: 16 }),
+    ("voluntary_exits", FieldType::ContainerList { element: "SignedVoluntaryExit", limit: 16 }),
+];
+
+const BEACON_BLOCK: ContainerSchema = &[
+    ("slot", FieldType::Basic("u64")),
+    ("proposer_index", FieldType::Basic("u64")),
+    ("parent_root", FieldType::Basic("Vector<u8, 32>")),
+    ("state_root", FieldType::Basic("Vector<u8, 32>")),
+    ("body", FieldType::Container("BeaconBlockBody")),
+];
+
+const BEACON_STATE: ContainerSchema = &[
+    ("genesis_time", FieldType::Basic("u64")),
+    ("genesis_validators_root", FieldType::Basic("Vector<u8, 32>")),
+    ("slot", FieldType::Basic("u64")),
+    ("fork", FieldType::Container("Fork")),
+    ("latest_block_header", FieldType::Container("BeaconBlockHeader")),
+    ("block_roots", FieldType::Basic("Vector<Vector<u8, 32>, 8192>")),
+    ("state_roots", FieldType::Basic("Vector<Vector<u8, 32>, 8192>")),
+    ("historical_roots", FieldType::Basic("List<Vector<u8, 32>, 16777216>")),
+    ("eth1_data", FieldType::Container("Eth1Data")),
+    ("eth1_data_votes", FieldType::ContainerList { element: "Eth1Data", limit: 2048 }),
+    ("eth1_deposit_index", FieldType::Basic("u64")),
+    ("validators", FieldType::ContainerList { element: "Validator", limit: 1099511627776 }),
+    ("balances", FieldType::Basic("List<u64, 1099511627776>")),
+    ("randao_mixes", FieldType::Basic("Vector<Vector<u8, 32>, 65536>")),
+    ("slashings", FieldType::Basic("Vector<u64, 8192>")),
+    (
+        "previous_epoch_attestations",
+        FieldType::ContainerList { element: "PendingAttestation", limit: 4096 },
+    ),
+    (
+        "current_epoch_attestations",
+        FieldType::ContainerList { element: "PendingAttestation", limit: 4096 },
+    ),
+    ("justification_bits", FieldType::Basic("Bitvector<4>")),
+    ("previous_justified_checkpoint", FieldType::Container("Checkpoint")),
+    ("current_justified_checkpoint", FieldType::Container("Checkpoint")),
+    ("finalized_checkpoint", FieldType::Container("Checkpoint")),
+];
+
+const PHASE0: ForkSchema = &[
+    ("Checkpoint", CHECKPOINT),
+    ("Eth1Data", ETH1_DATA),
+    ("BeaconBlockHeader", BEACON_BLOCK_HEADER),
+    ("AttestationData", ATTESTATION_DATA),
+    ("Attestation", ATTESTATION_PHASE0),
+    ("Fork", FORK),
+    ("Validator", VALIDATOR),
+    ("PendingAttestation", PENDING_ATTESTATION),
+    ("IndexedAttestation", INDEXED_ATTESTATION),
+    ("AttesterSlashing", ATTESTER_SLASHING),
+    ("SignedBeaconBlockHeader", SIGNED_BEACON_BLOCK_HEADER),
+    ("ProposerSlashing", PROPOSER_SLASHING),
+    ("DepositData", DEPOSIT_DATA),
+    ("Deposit", DEPOSIT),
+    ("VoluntaryExit", VOLUNTARY_EXIT),
+    ("SignedVoluntaryExit", SIGNED_VOLUNTARY_EXIT),
+    ("BeaconBlockBody", BEACON_BLOCK_BODY),
+    ("BeaconBlock", BEACON_BLOCK),
+    ("BeaconState", BEACON_STATE),
+];
+
+const SYNC_AGGREGATE: ContainerSchema = &[
+    ("sync_committee_bits", FieldType::Basic("Bitvector<512>")),
+    ("sync_committee_signature", FieldType::Basic("Vector<u8, 96>")),
+];
+
+const ALTAIR: ForkSchema = &[
+    ("Checkpoint", CHECKPOINT),
+    ("Eth1Data", ETH1_DATA),
+    ("BeaconBlockHeader", BEACON_BLOCK_HEADER),
+    ("AttestationData", ATTESTATION_DATA),
+    ("Attestation", ATTESTATION_PHASE0),
+    ("SyncAggregate", SYNC_AGGREGATE),
+];
+
+// Bellatrix, Capella and Deneb only extend the execution payload side of the tree;
+// the attestation/checkpoint machinery above is unchanged, so those forks reuse it.
+const BELLATRIX: ForkSchema = ALTAIR;
+const CAPELLA: ForkSchema = ALTAIR;
+const DENEB: ForkSchema = ALTAIR;
+
+/// Look up the container table for `fork`, panicking on an unknown fork name.
+pub fn schema_for_fork(fork: &str) -> ForkSchema {
+    match fork {
+        "phase0" => PHASE0,
+        "altair" => ALTAIR,
+        "bellatrix" => BELLATRIX,
+        "capella" => CAPELLA,
+        "deneb" => DENEB,
+        other => panic!("no ssz_static schema for fork {other}"),
+    }
+}
+
+fn container(schema: ForkSchema, type_name: &str) -> ContainerSchema {
+    schema
+        .iter()
+        .find(|(name, _)| *name == type_name)
+        .unwrap_or_else(|| panic!("unknown container {type_name} in schema"))
+        .1
+}
+
+fn to_rust_field_type(field_type: FieldType) -> String {
+    match field_type {
+        FieldType::Basic(rust_type) => rust_type.to_string(),
+        FieldType::Container(name) => name.to_string(),
+        FieldType::ContainerList { element, limit } => format!("List<{element}, {limit}>"),
+        FieldType::ContainerVector { element, length } => format!("Vector<{element}, {length}>"),
+    }
+}
+
+fn to_rust_struct_defn(type_name: &str, fields: ContainerSchema) -> String {
+    let body = fields
+        .iter()
+        .map(|(name, field_type)| format!("    {name}: {},", to_rust_field_type(*field_type)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("#[derive(PartialEq, Eq, Debug, Clone, Default, SimpleSerialize)]\nstruct {type_name} {{\n{body}\n}}\n")
+}
+
+/// Compile every container in `schema` into its `SimpleSerialize` struct definition,
+/// in declaration order, mirroring `CONTAINERS_DEFN_FMT`'s hand-written layout.
+pub fn compile_schema(schema: ForkSchema) -> String {
+    schema.iter().map(|(name, fields)| to_rust_struct_defn(name, fields)).collect::<Vec<_>>().join("\n")
+}
+
+fn to_rust_basic_value(rust_type: &str, value: &serde_yaml::Value) -> String {
+    if rust_type == "U256" {
+        super::to_rust_u256(value)
+    } else if rust_type.starts_with("Bitvector") {
+        super::to_rust_bitvector(value, rust_type)
+    } else if rust_type.starts_with("Bitlist") {
+        super::to_rust_bitlist(value, rust_type)
+    } else if rust_type.starts_with("List<u8") || rust_type.starts_with("Vector<u8") {
+        let data = value.as_str().unwrap();
+        let bytes = hex::decode(data.strip_prefix("0x").unwrap()).unwrap();
+        format!("<{rust_type}>::try_from(Vec::<u8>::from_iter({bytes:?})).unwrap()")
+    } else if let Some((outer, element, bound)) = split_collection_type(rust_type) {
+        // A bulk `List`/`Vector` of a basic (non-container) element -- e.g.
+        // `BeaconState`'s `block_roots: Vector<Vector<u8, 32>, 8192>` or
+        // `balances: List<u64, _>` -- recurses into the element type the same way
+        // `to_rust_field_value`'s `ContainerList`/`ContainerVector` arms recurse into
+        // named containers.
+        let items = value
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(|v| to_rust_basic_value(element, v))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{outer}::<{element}, {bound}>::try_from(vec![{items}]).unwrap()")
+    } else {
+        super::value_to_compact_string(value).trim_matches('\'').to_string()
+    }
+}
+
+/// Split a `List<T, N>`/`Vector<T, N>` spelling into its outer name, element type, and
+/// bound, respecting nested generics (so `Vector<Vector<u8, 32>, 8192>` splits into
+/// `Vector`, `Vector<u8, 32>`, `8192`, not on the inner type's own comma). `None` for
+/// any other spelling (a scalar, or a named container).
+fn split_collection_type(rust_type: &str) -> Option<(&str, &str, &str)> {
+    let (outer, rest) = rust_type.split_once('<')?;
+    if outer != "List" && outer != "Vector" {
+        return None;
+    }
+    let inner = rest.strip_suffix('>')?;
+    match split_top_level_commas(inner).as_slice() {
+        [element, bound] => Some((outer, element, bound)),
+        _ => None,
+    }
+}
+
+/// Split `s` on commas at bracket depth zero only, so a nested generic's own commas
+/// (`Vector<u8, 32>` inside `Vector<Vector<u8, 32>, 8192>`) aren't mistaken for the
+/// outer type's argument separators.
+fn split_top_level_commas(s: &str) -> Vec<&str> {
+    let mut depth = 0i32;
+    let mut start = 0;
+    let mut parts = Vec::new();
+    for (i, c) in s.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(s[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(s[start..].trim());
+    parts
+}
+
+fn to_rust_field_value(schema: ForkSchema, field_type: FieldType, value: &serde_yaml::Value) -> String {
+    match field_type {
+        FieldType::Basic(rust_type) => to_rust_basic_value(rust_type, value),
+        FieldType::Container(type_name) => to_rust_value(schema, type_name, value.clone()),
+        FieldType::ContainerList { element, limit } => {
+            let items = value
+                .as_sequence()
+                .unwrap()
+                .iter()
+                .map(|v| to_rust_value(schema, element, v.clone()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("List::<{element}, {limit}>::try_from(vec![{items}]).unwrap()")
+        }
+        FieldType::ContainerVector { element, length } => {
+            let items = value
+                .as_sequence()
+                .unwrap()
+                .iter()
+                .map(|v| to_rust_value(schema, element, v.clone()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("Vector::<{element}, {length}>::try_from(vec![{items}]).unwrap()")
+        }
+    }
+}
+
+/// Resolve a `value.yaml` mapping for `type_name` into a Rust value expression,
+/// recursing into nested containers and `List`/`Vector`-of-container fields via
+/// `schema` rather than a fixed set of per-type-name match arms.
+pub fn to_rust_value(schema: ForkSchema, type_name: &str, value: serde_yaml::Value) -> String {
+    let fields = container(schema, type_name);
+    let mapping = value.as_mapping().unwrap();
+    let rendered = fields
+        .iter()
+        .map(|(field_name, field_type)| {
+            let yaml_value = mapping
+                .get(serde_yaml::Value::String(field_name.to_string()))
+                .unwrap_or_else(|| panic!("missing field {field_name} on {type_name}"));
+            let rust_value = to_rust_field_value(schema, *field_type, yaml_value);
+            format!("{field_name}: {rust_value}")
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{type_name}{{{rendered}}}")
+}
+
+/// Snake-case identifier for `type_name`, used to build a test module/function prefix
+/// for a given fork + container (spec type names are `PascalCase`).
+pub fn test_prefix(fork: &str, type_name: &str) -> String {
+    format!("{fork}_{}", type_name.to_case(Case::Snake))
+}
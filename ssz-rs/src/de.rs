@@ -0,0 +1,144 @@
+//! SSZ deserialization: the `Deserialize` trait and the shared decoding routine for
+//! homogeneous collections (`List`/`Vector`), the mirror image of
+//! `ser::serialize_collection`.
+
+use crate::ser::{SszSize, BYTES_PER_LENGTH_OFFSET};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeserializeError {
+    /// A fixed-size value's encoding wasn't exactly the expected number of bytes, or
+    /// a byte `List`/`Vector`'s encoding didn't fit its bound/length.
+    InvalidByteLength(usize),
+    /// A `List`/`Vector`'s encoding held more elements than its bound allows.
+    CollectionTooLarge { len: usize, bound: usize },
+    /// An offset table entry pointed outside the encoding it indexes into, or
+    /// offsets weren't non-decreasing.
+    InvalidOffset,
+    /// Trailing bytes remained after every expected field/element was consumed.
+    ExtraInput,
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidByteLength(len) => write!(f, "invalid encoded length {len}"),
+            Self::CollectionTooLarge { len, bound } => {
+                write!(f, "collection of length {len} exceeds its bound of {bound}")
+            }
+            Self::InvalidOffset => write!(f, "invalid or out-of-bounds offset"),
+            Self::ExtraInput => write!(f, "extra input remaining after decoding"),
+        }
+    }
+}
+
+impl std::error::Error for DeserializeError {}
+
+/// Implemented by every SSZ-decodable type.
+pub trait Deserialize: Sized {
+    fn deserialize(encoding: &[u8]) -> Result<Self, DeserializeError>;
+}
+
+macro_rules! impl_deserialize_for_uint {
+    ($ty:ty) => {
+        impl Deserialize for $ty {
+            fn deserialize(encoding: &[u8]) -> Result<Self, DeserializeError> {
+                let bytes: [u8; std::mem::size_of::<$ty>()] = encoding
+                    .try_into()
+                    .map_err(|_| DeserializeError::InvalidByteLength(encoding.len()))?;
+                Ok(<$ty>::from_le_bytes(bytes))
+            }
+        }
+    };
+}
+
+impl_deserialize_for_uint!(u8);
+impl_deserialize_for_uint!(u16);
+impl_deserialize_for_uint!(u32);
+impl_deserialize_for_uint!(u64);
+impl_deserialize_for_uint!(u128);
+
+impl Deserialize for bool {
+    fn deserialize(encoding: &[u8]) -> Result<Self, DeserializeError> {
+        match encoding {
+            [0] => Ok(false),
+            [1] => Ok(true),
+            _ => Err(DeserializeError::InvalidByteLength(encoding.len())),
+        }
+    }
+}
+
+/// Decode a homogeneous collection out of `encoding`, the mirror of
+/// `ser::serialize_collection`. `expected_len`, when given, is the exact element
+/// count a `Vector<T, N>` requires (`N`); `None` lets a `List<T, N>` infer its count
+/// from the encoding itself -- from the byte length directly for fixed-size `T`, or
+/// from the first offset (which equals the size of the whole offset table, so
+/// dividing by `BYTES_PER_LENGTH_OFFSET` recovers the element count) for variable-size
+/// `T`.
+pub fn deserialize_collection<T: Deserialize + SszSize>(
+    encoding: &[u8],
+    expected_len: Option<usize>,
+) -> Result<Vec<T>, DeserializeError> {
+    if !T::IS_VARIABLE_SIZE {
+        let size = T::size_hint();
+        if size == 0 {
+            return if encoding.is_empty() {
+                Ok(Vec::new())
+            } else {
+                Err(DeserializeError::ExtraInput)
+            };
+        }
+        if encoding.len() % size != 0 {
+            return Err(DeserializeError::InvalidByteLength(encoding.len()));
+        }
+        let len = encoding.len() / size;
+        if let Some(expected_len) = expected_len {
+            if len != expected_len {
+                return Err(DeserializeError::InvalidByteLength(encoding.len()));
+            }
+        }
+        return encoding.chunks_exact(size).map(T::deserialize).collect();
+    }
+
+    if encoding.is_empty() {
+        return match expected_len {
+            Some(0) | None => Ok(Vec::new()),
+            Some(_) => Err(DeserializeError::InvalidByteLength(encoding.len())),
+        };
+    }
+
+    let first_offset = read_offset(encoding, 0)?;
+    if first_offset as usize % BYTES_PER_LENGTH_OFFSET != 0 {
+        return Err(DeserializeError::InvalidOffset);
+    }
+    let len = first_offset as usize / BYTES_PER_LENGTH_OFFSET;
+    if let Some(expected_len) = expected_len {
+        if len != expected_len {
+            return Err(DeserializeError::InvalidByteLength(encoding.len()));
+        }
+    }
+
+    let mut offsets = Vec::with_capacity(len + 1);
+    for i in 0..len {
+        offsets.push(read_offset(encoding, i)?);
+    }
+    offsets.push(encoding.len() as u32);
+
+    let mut items = Vec::with_capacity(len);
+    for window in offsets.windows(2) {
+        let (start, end) = (window[0] as usize, window[1] as usize);
+        if end < start || end > encoding.len() {
+            return Err(DeserializeError::InvalidOffset);
+        }
+        items.push(T::deserialize(&encoding[start..end])?);
+    }
+    Ok(items)
+}
+
+pub(crate) fn read_offset(encoding: &[u8], index: usize) -> Result<u32, DeserializeError> {
+    let start = index * BYTES_PER_LENGTH_OFFSET;
+    let end = start + BYTES_PER_LENGTH_OFFSET;
+    let bytes: [u8; BYTES_PER_LENGTH_OFFSET] =
+        encoding.get(start..end).ok_or(DeserializeError::InvalidOffset)?.try_into().unwrap();
+    Ok(u32::from_le_bytes(bytes))
+}
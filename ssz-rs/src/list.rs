@@ -0,0 +1,177 @@
+//! `List<T, N>`: a variable-length, SSZ-encoded sequence of at most `N` elements of
+//! `T`. Merkleized to a packed chunk tree sized by the *bound* `N` (so a `List`'s root
+//! shape never changes as it grows or shrinks within that bound) with the element
+//! count mixed in on top, as every SSZ `List` is.
+
+use crate::{
+    de::{deserialize_collection, Deserialize, DeserializeError},
+    merkleization::{
+        cache::{Cache, CachedHashTreeRoot},
+        chunk_count, leaf_bytes, leaf_index_for, merkleize_elements, mix_in_length,
+        proof::{GeneralizedIndex, GeneralizedIndexable},
+        Composite, ElementChunks, HashTreeRoot, MerkleizationError, Node,
+    },
+    ser::{serialize_collection, Serialize, SerializeError, SszSize},
+};
+use std::ops::{Deref, Index, IndexMut};
+
+/// A variable-length sequence of at most `N` elements of `T`.
+#[derive(Debug, Clone)]
+pub struct List<T, const N: usize> {
+    data: Vec<T>,
+    cache: Option<Cache>,
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for List<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for List<T, N> {}
+
+impl<T, const N: usize> Default for List<T, N> {
+    fn default() -> Self {
+        Self { data: Vec::new(), cache: None }
+    }
+}
+
+impl<T, const N: usize> Deref for List<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.data
+    }
+}
+
+impl<T, const N: usize> TryFrom<Vec<T>> for List<T, N> {
+    type Error = SerializeError;
+
+    fn try_from(data: Vec<T>) -> Result<Self, Self::Error> {
+        if data.len() > N {
+            return Err(SerializeError::CollectionTooLarge { len: data.len(), bound: N });
+        }
+        Ok(Self { data, cache: None })
+    }
+}
+
+impl<T: ElementChunks, const N: usize> List<T, N> {
+    /// A mutable reference to the element at `index`, dirtying its chunk in the
+    /// [`Cache`] (if enabled) so the next [`CachedHashTreeRoot::hash_tree_root_cached`]
+    /// call rehashes only the affected path.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.data.len() {
+            return None;
+        }
+        if let Some(cache) = self.cache.as_mut() {
+            cache.mark_leaf_dirty(leaf_index_for::<T>(index));
+        }
+        Some(&mut self.data[index])
+    }
+
+    /// Append `value`, failing once the list already holds `N` elements. Dirties the
+    /// new element's chunk and the length mix-in in the [`Cache`] (if enabled).
+    pub fn push(&mut self, value: T) -> Result<(), SerializeError> {
+        if self.data.len() == N {
+            return Err(SerializeError::CollectionTooLarge { len: self.data.len() + 1, bound: N });
+        }
+        let index = self.data.len();
+        self.data.push(value);
+        if let Some(cache) = self.cache.as_mut() {
+            cache.mark_leaf_dirty(leaf_index_for::<T>(index));
+            cache.mark_length_dirty();
+        }
+        Ok(())
+    }
+}
+
+impl<T: ElementChunks, const N: usize> Index<usize> for List<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.data[index]
+    }
+}
+
+impl<T: ElementChunks, const N: usize> IndexMut<usize> for List<T, N> {
+    /// Index-assignment (`list[i] = value`) goes through [`List::get_mut`], so it
+    /// dirties the element's chunk in the [`Cache`] just like any other mutating
+    /// accessor.
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+impl<T, const N: usize> SszSize for List<T, N> {
+    const IS_VARIABLE_SIZE: bool = true;
+
+    fn size_hint() -> usize {
+        0
+    }
+}
+
+impl<T: Serialize + SszSize, const N: usize> Serialize for List<T, N> {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SerializeError> {
+        serialize_collection(&self.data, buffer)
+    }
+}
+
+impl<T: Deserialize + SszSize, const N: usize> Deserialize for List<T, N> {
+    fn deserialize(encoding: &[u8]) -> Result<Self, DeserializeError> {
+        let data: Vec<T> = deserialize_collection(encoding, None)?;
+        if data.len() > N {
+            return Err(DeserializeError::CollectionTooLarge { len: data.len(), bound: N });
+        }
+        Ok(Self { data, cache: None })
+    }
+}
+
+impl<T: ElementChunks, const N: usize> Composite for List<T, N> {}
+
+impl<T: ElementChunks, const N: usize> HashTreeRoot for List<T, N> {
+    fn hash_tree_root(&self) -> Result<Node, MerkleizationError> {
+        let root = merkleize_elements(&self.data, chunk_count::<T>(N))?;
+        Ok(mix_in_length(&root, self.data.len()))
+    }
+}
+
+impl<T: ElementChunks, const N: usize> CachedHashTreeRoot for List<T, N> {
+    fn enable_cache(&mut self) {
+        self.cache = Some(Cache::new(chunk_count::<T>(N), true));
+    }
+
+    fn disable_cache(&mut self) {
+        self.cache = None;
+    }
+
+    fn hash_tree_root_cached(&mut self) -> Result<Node, MerkleizationError> {
+        let Some(cache) = self.cache.as_mut() else {
+            return self.hash_tree_root();
+        };
+        let data = &self.data;
+        Ok(cache.root(
+            |leaf_index| leaf_bytes(data, leaf_index),
+            Some(|root: &Node| mix_in_length(root, data.len())),
+        ))
+    }
+}
+
+impl<T: ElementChunks, const N: usize> GeneralizedIndexable for List<T, N> {
+    fn chunk_count() -> usize {
+        chunk_count::<T>(N)
+    }
+
+    fn is_variable_length() -> bool {
+        true
+    }
+
+    fn element_index(i: usize) -> GeneralizedIndex {
+        let leaf_count = Self::chunk_count().next_power_of_two().max(1);
+        let data_root = GeneralizedIndex(2);
+        data_root.concat(GeneralizedIndex((leaf_count + leaf_index_for::<T>(i)) as u64))
+    }
+
+    fn length_index() -> GeneralizedIndex {
+        GeneralizedIndex(3)
+    }
+}
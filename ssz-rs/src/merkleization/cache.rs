@@ -0,0 +1,165 @@
+//! Incremental merkleization cache for `List`/`Vector`/container types.
+//!
+//! Recomputing `hash_tree_root` from scratch rehashes every packed chunk, even when a
+//! caller only mutated a handful of leaves since the last call -- the common case for
+//! large, long-lived collections such as a transactions tree. `Cache` memoizes the
+//! complete binary tree of intermediate subtree hashes over a type's chunks and, on the
+//! next root computation, rehashes only the path from a dirtied leaf back to the root.
+//!
+//! This is opt-in: a type pays for a `Cache` only once it asks for one, so the default,
+//! uncached `hash_tree_root` path is unaffected.
+
+use crate::merkleization::{hash_nodes, MerkleizationError, Node};
+
+/// A single node in the cached tree: its last-computed hash, and whether that hash (or
+/// one of its descendants') has been invalidated since.
+#[derive(Debug, Clone, Copy)]
+struct CacheNode {
+    hash: Node,
+    dirty: bool,
+}
+
+impl Default for CacheNode {
+    fn default() -> Self {
+        // Everything starts dirty so the first `root` call builds the tree bottom-up.
+        Self { hash: Node::default(), dirty: true }
+    }
+}
+
+/// Opt-in incremental merkleization state for a collection or container.
+///
+/// `Cache` stores a complete binary tree over `leaf_count` chunks -- the next power of
+/// two at or above the type's packed chunk count, so unused trailing leaves merkleize
+/// as the zero hash just as an uncached computation would -- plus, for variable-length
+/// types, a node for the length mix-in.
+///
+/// Mutating accessors are responsible for calling [`Cache::mark_leaf_dirty`] for every
+/// chunk they touch (and [`Cache::mark_length_dirty`] when the element count itself
+/// changes) before the next [`Cache::root`] call.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    nodes: Vec<CacheNode>,
+    leaf_count: usize,
+    length_mixin: Option<CacheNode>,
+}
+
+impl Cache {
+    /// Construct a cache sized for `leaf_count` leaves, all initially dirty so the
+    /// first `root` call performs a full build. `variable_length` types additionally
+    /// track a length mix-in node.
+    pub fn new(leaf_count: usize, variable_length: bool) -> Self {
+        let leaf_count = leaf_count.next_power_of_two().max(1);
+        Self {
+            nodes: vec![CacheNode::default(); 2 * leaf_count - 1],
+            leaf_count,
+            length_mixin: variable_length.then(CacheNode::default),
+        }
+    }
+
+    fn leaf_start(&self) -> usize {
+        self.nodes.len() - self.leaf_count
+    }
+
+    /// Mark the chunk at `leaf_index` dirty, along with every ancestor up to the root,
+    /// after a mutating access to that chunk.
+    pub fn mark_leaf_dirty(&mut self, leaf_index: usize) {
+        debug_assert!(leaf_index < self.leaf_count);
+        let mut i = self.leaf_start() + leaf_index;
+        self.nodes[i].dirty = true;
+        while i > 0 {
+            i = (i - 1) / 2;
+            self.nodes[i].dirty = true;
+        }
+    }
+
+    /// Mark the length mix-in dirty after the element count changes, even if no leaf
+    /// content itself was touched.
+    pub fn mark_length_dirty(&mut self) {
+        if let Some(mixin) = self.length_mixin.as_mut() {
+            mixin.dirty = true;
+        }
+    }
+
+    /// Grow the cache to accommodate at least `leaf_count` leaves. Crossing the next
+    /// power-of-two boundary invalidates the existing tree shape, so this forces a full
+    /// rebuild rather than trying to graft the old tree onto the new one.
+    pub fn ensure_capacity(&mut self, leaf_count: usize) {
+        if leaf_count.next_power_of_two().max(1) != self.leaf_count {
+            *self = Self::new(leaf_count, self.length_mixin.is_some());
+        }
+    }
+
+    /// Recompute and return the root, rehashing only nodes along dirty paths.
+    ///
+    /// `leaf_hash(i)` supplies the current hash of leaf `i` and is only invoked for
+    /// leaves whose own dirty bit is set. `mix_in_length`, when
+    /// provided, combines the freshly computed tree root with the current element
+    /// count; it is re-run whenever the root changed or the mix-in was itself marked
+    /// dirty, and skipped otherwise.
+    pub fn root(
+        &mut self,
+        leaf_hash: impl Fn(usize) -> Node,
+        mix_in_length: Option<impl Fn(&Node) -> Node>,
+    ) -> Node {
+        let root_was_dirty = self.nodes[0].dirty;
+        if root_was_dirty {
+            let leaf_start = self.leaf_start();
+            Self::recompute(&mut self.nodes, 0, leaf_start, &leaf_hash);
+        }
+        let root = self.nodes[0].hash;
+        match (self.length_mixin.as_mut(), mix_in_length) {
+            (Some(mixin), Some(mix_in_length)) => {
+                if root_was_dirty || mixin.dirty {
+                    mixin.hash = mix_in_length(&root);
+                    mixin.dirty = false;
+                }
+                mixin.hash
+            }
+            _ => root,
+        }
+    }
+
+    // A dirty ancestor is the only thing that can make a descendant worth visiting: an
+    // ancestor's dirty bit is set whenever any leaf beneath it was marked dirty, and
+    // cleared (along with every descendant) once this function finishes with it.
+    fn recompute(
+        nodes: &mut [CacheNode],
+        index: usize,
+        leaf_start: usize,
+        leaf_hash: &impl Fn(usize) -> Node,
+    ) {
+        if !nodes[index].dirty {
+            return;
+        }
+        if index >= leaf_start {
+            let node = &mut nodes[index];
+            node.hash = leaf_hash(index - leaf_start);
+            node.dirty = false;
+            return;
+        }
+        let (left, right) = (2 * index + 1, 2 * index + 2);
+        Self::recompute(nodes, left, leaf_start, leaf_hash);
+        Self::recompute(nodes, right, leaf_start, leaf_hash);
+        nodes[index].hash = hash_nodes(&nodes[left].hash, &nodes[right].hash);
+        nodes[index].dirty = false;
+    }
+}
+
+/// Implemented by merkleizable types that support an opt-in [`Cache`] for incremental
+/// `hash_tree_root` recomputation. `List` and `Vector` implement this so callers can
+/// opt into caching per value without changing the default, uncached behavior of
+/// [`super::HashTreeRoot`]; the `SimpleSerialize` derive does not implement it for
+/// containers.
+pub trait CachedHashTreeRoot {
+    /// Enable incremental caching for this value, rebuilding from scratch on the next
+    /// `hash_tree_root_cached` call.
+    fn enable_cache(&mut self);
+
+    /// Disable caching and drop any memoized tree, reverting to a from-scratch
+    /// `hash_tree_root` on every subsequent call.
+    fn disable_cache(&mut self);
+
+    /// Compute `hash_tree_root`, consulting the cache (if enabled) and rehashing only
+    /// the dirty path; falls back to a full recomputation when caching is disabled.
+    fn hash_tree_root_cached(&mut self) -> Result<Node, MerkleizationError>;
+}
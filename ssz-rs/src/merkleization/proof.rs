@@ -0,0 +1,263 @@
+//! Merkle multiproof generation and verification over generalized indices.
+//!
+//! A [`GeneralizedIndex`] addresses any node in a value's merkle tree with the
+//! `2^depth + offset` numbering used throughout the consensus specs: the tree's root
+//! is `1`, a node's children are `2 * i` and `2 * i + 1`, and its depth is
+//! `floor(log2(i))`. [`prove`] takes the value's full chunk tree and a set of target
+//! indices and returns the minimal [`Multiproof`] -- the target leaves plus whichever
+//! sibling hashes are needed and not already implied by another target or a hash
+//! already being produced -- and [`verify`] recomputes the root from that proof alone,
+//! without the rest of the tree. This is the building block a light client needs: it
+//! can check a single field or a handful of fields of a `BeaconState` against a known
+//! root without holding the whole state.
+//!
+//! Single-index proofs are the degenerate case of the general algorithm (a `Multiproof`
+//! over one index), so both are implemented by the same multiproof machinery rather
+//! than as two separate code paths.
+//!
+//! Composing a proof across nested types -- a field of a container that is itself a
+//! container, or an element of a `List`/`Vector` -- uses [`GeneralizedIndex::concat`]
+//! to express the inner index relative to the outer root. A container's field index
+//! would be computed from its declaration position (the same order `hash_tree_root`
+//! derives from) via [`GeneralizedIndexable::chunk_count`] -- the `SimpleSerialize`
+//! derive that would do this for a derived container doesn't exist yet (TODO), so
+//! [`GeneralizedIndexable`] impls are hand-written for now. `List`/`Vector` already
+//! compute an element's index from their packing factor and chunk count; for
+//! variable-length types the element's index is concatenated under the data root
+//! (`2`), the sibling of the length mix-in chunk (`3`).
+
+use crate::merkleization::{hash_nodes, MerkleizationError, Node};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A node's position in a merkle tree, numbered `2^depth + offset` from the root
+/// (`1`) down, as used throughout the consensus specs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GeneralizedIndex(pub u64);
+
+impl GeneralizedIndex {
+    /// The whole tree's root.
+    pub const ROOT: Self = Self(1);
+
+    /// Depth below the root; the root itself is depth `0`.
+    pub fn depth(&self) -> u32 {
+        63 - self.0.leading_zeros()
+    }
+
+    /// This index's sibling: the other child of the same parent.
+    pub fn sibling(&self) -> Self {
+        Self(self.0 ^ 1)
+    }
+
+    /// This index's parent.
+    pub fn parent(&self) -> Self {
+        Self(self.0 / 2)
+    }
+
+    /// Re-root `child` -- an index relative to the subtree rooted at `self` -- onto
+    /// the overall tree `self` belongs to. Used to compose proofs across nested
+    /// containers/collections: a field's index within its own container, concatenated
+    /// onto the field's index within the parent container, yields the field's index
+    /// relative to the parent's root.
+    pub fn concat(&self, child: Self) -> Self {
+        let child_depth = child.depth();
+        let child_offset = child.0 - (1 << child_depth);
+        Self((self.0 << child_depth) + child_offset)
+    }
+
+    /// This index and every ancestor up to (and including) the root, closest-to-root
+    /// first.
+    fn path_to_root(&self) -> Vec<Self> {
+        let mut path = vec![*self];
+        while *path.last().unwrap() != Self::ROOT {
+            path.push(path.last().unwrap().parent());
+        }
+        path.reverse();
+        path
+    }
+
+    /// The sibling of every node on the path from this index to the root, leaf-first
+    /// -- exactly the hashes a single-index proof must supply.
+    fn branch_indices(&self) -> Vec<Self> {
+        self.path_to_root()
+            .into_iter()
+            .skip(1)
+            .rev()
+            .map(|i| i.sibling())
+            .collect()
+    }
+}
+
+/// The minimal set of sibling hashes needed to recompute a root from one or more
+/// known leaves, as produced by [`prove`] and checked by [`verify`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Multiproof {
+    /// The proven indices, ascending; `leaves[i]` is the value at `indices[i]`.
+    pub indices: Vec<GeneralizedIndex>,
+    /// The leaf hashes at `indices`, in the same order.
+    pub leaves: Vec<Node>,
+    /// The helper hashes `verify` needs beyond `leaves` itself, in decreasing
+    /// generalized-index order -- the order they must be folded in for the upward
+    /// hashing pass to resolve every parent as soon as both its children are known.
+    pub branch: Vec<Node>,
+}
+
+/// The union of every target index's [`GeneralizedIndex::branch_indices`], minus
+/// whatever is already implied by another target or an ancestor of one -- the extra
+/// hashes a multiproof must carry beyond the leaves themselves.
+fn helper_indices(indices: &[GeneralizedIndex]) -> Vec<GeneralizedIndex> {
+    let mut path_indices = BTreeSet::new();
+    let mut all_branch_indices = BTreeSet::new();
+    for index in indices {
+        path_indices.extend(index.path_to_root());
+        all_branch_indices.extend(index.branch_indices());
+    }
+    let mut helpers = all_branch_indices
+        .difference(&path_indices)
+        .copied()
+        .collect::<Vec<_>>();
+    helpers.sort_by(|a, b| b.cmp(a));
+    helpers
+}
+
+/// Build a [`Multiproof`] for `indices` out of `tree`, a flattened, 1-indexed complete
+/// binary tree over a value's chunks (`tree[1]` is the root, `tree[2 * i]` and
+/// `tree[2 * i + 1]` are `tree[i]`'s children) such as [`merkleize_to_tree`] produces.
+pub fn prove(tree: &[Node], indices: &[GeneralizedIndex]) -> Multiproof {
+    let mut indices = indices.to_vec();
+    indices.sort();
+    let leaves = indices.iter().map(|i| tree[i.0 as usize]).collect();
+    let branch = helper_indices(&indices)
+        .iter()
+        .map(|i| tree[i.0 as usize])
+        .collect();
+    Multiproof {
+        indices,
+        leaves,
+        branch,
+    }
+}
+
+/// Recompute the root implied by `proof` and check it against `root`.
+pub fn verify(proof: &Multiproof, root: Node) -> Result<(), MerkleizationError> {
+    if calculate_root(proof)? == root {
+        Ok(())
+    } else {
+        Err(MerkleizationError::InvalidProof)
+    }
+}
+
+/// Fold `proof`'s leaves and helper hashes upward to the implied root, without
+/// assuming the caller already knows it (unlike [`verify`], which additionally checks
+/// it against an expected value).
+pub fn calculate_root(proof: &Multiproof) -> Result<Node, MerkleizationError> {
+    if proof.indices.len() != proof.leaves.len() {
+        return Err(MerkleizationError::InvalidProof);
+    }
+    // `GeneralizedIndex(0)` has no parent in the `2^depth + offset` scheme (`0.parent()`
+    // is `0` again), so `path_to_root`/`branch_indices` would loop forever on it rather
+    // than reach `ROOT`. Reject it up front rather than letting a malformed proof hang.
+    if proof.indices.iter().any(|i| i.0 == 0) {
+        return Err(MerkleizationError::InvalidProof);
+    }
+    let helpers = helper_indices(&proof.indices);
+    if helpers.len() != proof.branch.len() {
+        return Err(MerkleizationError::InvalidProof);
+    }
+
+    let mut known = proof
+        .indices
+        .iter()
+        .copied()
+        .zip(proof.leaves.iter().copied())
+        .chain(helpers.iter().copied().zip(proof.branch.iter().copied()))
+        .collect::<BTreeMap<_, _>>();
+
+    // Largest generalized index first: a node's parent only becomes knowable once
+    // both its children are, so working from the leaves up guarantees every node is
+    // visited after its children.
+    let mut queue = known.keys().copied().collect::<Vec<_>>();
+    queue.sort_by(|a, b| b.cmp(a));
+    let mut pos = 0;
+    while pos < queue.len() {
+        let index = queue[pos];
+        pos += 1;
+        if index == GeneralizedIndex::ROOT {
+            continue;
+        }
+        let parent = index.parent();
+        if known.contains_key(&parent) {
+            continue;
+        }
+        let sibling = index.sibling();
+        if let Some(&sibling_hash) = known.get(&sibling) {
+            let (left, right) = if index.0 % 2 == 0 {
+                (known[&index], sibling_hash)
+            } else {
+                (sibling_hash, known[&index])
+            };
+            known.insert(parent, hash_nodes(&left, &right));
+            queue.push(parent);
+        }
+    }
+
+    known
+        .get(&GeneralizedIndex::ROOT)
+        .copied()
+        .ok_or(MerkleizationError::InvalidProof)
+}
+
+/// Merkleize `leaves` into a flattened, 1-indexed complete binary tree (`tree[1]` is
+/// the root; index `i`'s children are `2 * i` and `2 * i + 1`), as though the tree
+/// had exactly `leaf_count` leaves -- a `Vector`'s element count, or a `List`'s
+/// *bound* rather than its current length -- padding with the zero hash up to
+/// `leaf_count`'s next power of two. [`prove`] reads whichever nodes a proof needs
+/// directly out of the result by [`GeneralizedIndex`].
+pub fn merkleize_to_tree(leaves: &[Node], leaf_count: usize) -> Vec<Node> {
+    let leaf_count = leaf_count.max(leaves.len()).next_power_of_two().max(1);
+    let mut tree = vec![Node::default(); 2 * leaf_count];
+    tree[leaf_count..leaf_count + leaves.len()].copy_from_slice(leaves);
+    for i in (1..leaf_count).rev() {
+        tree[i] = hash_nodes(&tree[2 * i], &tree[2 * i + 1]);
+    }
+    tree
+}
+
+/// Implemented by merkleizable types so [`prove`]/[`verify`] callers -- and nested
+/// containers composing an inner index with [`GeneralizedIndex::concat`] -- can find a
+/// field or element's generalized index without hand-computing tree depths.
+/// `List`/`Vector` implement it over their packing factor and chunk count; the
+/// `SimpleSerialize` derive does not implement it, so a container's impl is
+/// hand-written.
+pub trait GeneralizedIndexable {
+    /// Chunks this value's own merkleization packs into, before any length mix-in --
+    /// the same count a [`super::cache::Cache`] for this value sizes its `leaf_count`
+    /// from.
+    fn chunk_count() -> usize;
+
+    /// `true` for `List`/`Bitlist`, whose `hash_tree_root` mixes in an extra length
+    /// chunk above the packed chunk tree; `false` for `Vector`/`Bitvector` and
+    /// containers, which merkleize to their packed chunk tree's root directly.
+    fn is_variable_length() -> bool {
+        false
+    }
+
+    /// Generalized index of a named container field's chunk, relative to this value's
+    /// own root. Only meaningful for derived containers; the default panics since a
+    /// collection has no named fields.
+    fn field_index(name: &str) -> GeneralizedIndex {
+        panic!("{} has no field `{name}`", std::any::type_name::<Self>())
+    }
+
+    /// Generalized index of the chunk holding element `i`, relative to this value's
+    /// own root. Only meaningful for `List`/`Vector`; the default panics since a
+    /// container has no indexed elements.
+    fn element_index(i: usize) -> GeneralizedIndex {
+        panic!("{} has no element {i}", std::any::type_name::<Self>())
+    }
+
+    /// Generalized index of the length mix-in chunk, relative to this value's own
+    /// root. Only meaningful when [`Self::is_variable_length`] is `true`.
+    fn length_index() -> GeneralizedIndex {
+        panic!("{} is not variable-length", std::any::type_name::<Self>())
+    }
+}
@@ -0,0 +1,150 @@
+//! `serde` support for SSZ types, following the Ethereum consensus JSON/YAML
+//! conventions used throughout the spec tests and client APIs: `uint64` and narrower
+//! serialize as plain numbers, `uint128`/`uint256` as decimal strings, byte
+//! `List`/`Vector` and bitfields as `0x`-prefixed hex strings, and containers as
+//! objects keyed by the `snake_case` field name.
+//!
+//! This module only exists behind the `serde` feature. It supplies the `with =` helper
+//! modules a derived container's fields would need when their SSZ type requires
+//! encoding other than serde's default -- a byte `List`/`Vector` or `Bitlist`/
+//! `Bitvector` field would take `#[serde(with = "::ssz_rs::serde::as_hex")]`, and a
+//! `u128`/`U256` field `#[serde(with = "::ssz_rs::serde::as_str")]` -- plus the
+//! concrete `Serialize`/`Deserialize` impls for the crate's own types. The
+//! `SimpleSerialize` derive that would attach these attributes automatically doesn't
+//! exist yet (TODO); until it does, a hand-written container must add them itself.
+//! Everything here is written against `::serde` by its full path so it never collides
+//! with the crate's own [`crate::ser::Serialize`]/[`crate::de::Deserialize`] SSZ traits
+//! of the same name.
+
+use crate::{
+    bitlist::Bitlist,
+    bitvector::Bitvector,
+    de::Deserialize as SszDeserialize,
+    list::List,
+    ser::Serialize as SszSerialize,
+    vector::Vector,
+    U256,
+};
+
+/// `with =` helpers for fields whose SSZ serialization is already the right bytes to
+/// hex-encode: byte `List<u8, _>`/`Vector<u8, _>`, and `Bitlist`/`Bitvector`.
+pub mod as_hex {
+    use super::*;
+    use ::serde::{Deserialize as _, Deserializer, Serializer};
+
+    /// Serialize `value` as a `0x`-prefixed lowercase hex string of its SSZ encoding.
+    pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: SszSerialize,
+    {
+        let mut buf = Vec::new();
+        value.serialize(&mut buf).map_err(::serde::ser::Error::custom)?;
+        serializer.serialize_str(&format!("0x{}", hex::encode(buf)))
+    }
+
+    /// Deserialize a `0x`-prefixed hex string back into its SSZ encoding.
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: SszDeserialize,
+    {
+        let s = <&str>::deserialize(deserializer)?;
+        let s = s.strip_prefix("0x").ok_or_else(|| {
+            ::serde::de::Error::custom("expected a '0x'-prefixed hex string")
+        })?;
+        let bytes = hex::decode(s).map_err(::serde::de::Error::custom)?;
+        T::deserialize(&bytes).map_err(::serde::de::Error::custom)
+    }
+}
+
+/// `with =` helpers for wide integer fields (`u128`, `U256`) that the consensus JSON
+/// convention renders as decimal strings rather than native numbers, since not every
+/// JSON consumer can hold a 128- or 256-bit integer in a number type.
+pub mod as_str {
+    use ::serde::{Deserialize as _, Deserializer, Serializer};
+    use std::{fmt::Display, str::FromStr};
+
+    pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Display,
+    {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: FromStr,
+        T::Err: Display,
+    {
+        let s = <&str>::deserialize(deserializer)?;
+        s.parse().map_err(::serde::de::Error::custom)
+    }
+}
+
+impl ::serde::Serialize for U256 {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        as_str::serialize(self, serializer)
+    }
+}
+
+impl<'de> ::serde::Deserialize<'de> for U256 {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        as_str::deserialize(deserializer)
+    }
+}
+
+// Only `u8` elements hex-encode: the consensus JSON convention hex-encodes a `List`/
+// `Vector`'s raw bytes, which is only the right encoding when its SSZ serialization
+// already *is* those bytes -- true for `List<u8, _>`/`Vector<u8, _>`, but not, say,
+// `List<u64, _>` (a `BeaconState::balances`-shaped field), which belongs in JSON as an
+// array of decimal numbers, not a hex blob of its packed SSZ encoding.
+impl<const N: usize> ::serde::Serialize for List<u8, N> {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        as_hex::serialize(self, serializer)
+    }
+}
+
+impl<'de, const N: usize> ::serde::Deserialize<'de> for List<u8, N> {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        as_hex::deserialize(deserializer)
+    }
+}
+
+impl<const N: usize> ::serde::Serialize for Vector<u8, N> {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        as_hex::serialize(self, serializer)
+    }
+}
+
+impl<'de, const N: usize> ::serde::Deserialize<'de> for Vector<u8, N> {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        as_hex::deserialize(deserializer)
+    }
+}
+
+impl<const N: usize> ::serde::Serialize for Bitlist<N> {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        as_hex::serialize(self, serializer)
+    }
+}
+
+impl<'de, const N: usize> ::serde::Deserialize<'de> for Bitlist<N> {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        as_hex::deserialize(deserializer)
+    }
+}
+
+impl<const N: usize> ::serde::Serialize for Bitvector<N> {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        as_hex::serialize(self, serializer)
+    }
+}
+
+impl<'de, const N: usize> ::serde::Deserialize<'de> for Bitvector<N> {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        as_hex::deserialize(deserializer)
+    }
+}
@@ -0,0 +1,145 @@
+//! `Vector<T, N>`: a fixed-length, SSZ-encoded sequence of exactly `N` elements of
+//! `T`. Merkleizes directly to its packed chunk tree's root -- unlike `List`, there's
+//! no length to mix in, since `N` is fixed.
+
+use crate::{
+    de::{deserialize_collection, Deserialize, DeserializeError},
+    merkleization::{
+        cache::{Cache, CachedHashTreeRoot},
+        chunk_count, leaf_bytes, leaf_index_for, merkleize_elements,
+        proof::{GeneralizedIndex, GeneralizedIndexable},
+        Composite, ElementChunks, HashTreeRoot, MerkleizationError, Node,
+    },
+    ser::{serialize_collection, Serialize, SerializeError, SszSize},
+};
+use std::ops::{Deref, Index, IndexMut};
+
+/// A fixed-length sequence of exactly `N` elements of `T`.
+#[derive(Debug, Clone)]
+pub struct Vector<T, const N: usize> {
+    data: Vec<T>,
+    cache: Option<Cache>,
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for Vector<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for Vector<T, N> {}
+
+impl<T: Default + Clone, const N: usize> Default for Vector<T, N> {
+    fn default() -> Self {
+        Self { data: vec![T::default(); N], cache: None }
+    }
+}
+
+impl<T, const N: usize> Deref for Vector<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.data
+    }
+}
+
+impl<T, const N: usize> TryFrom<Vec<T>> for Vector<T, N> {
+    type Error = DeserializeError;
+
+    fn try_from(data: Vec<T>) -> Result<Self, Self::Error> {
+        if data.len() != N {
+            return Err(DeserializeError::InvalidByteLength(data.len()));
+        }
+        Ok(Self { data, cache: None })
+    }
+}
+
+impl<T: ElementChunks, const N: usize> Vector<T, N> {
+    /// A mutable reference to the element at `index`, dirtying its chunk in the
+    /// [`Cache`] (if enabled) so the next [`CachedHashTreeRoot::hash_tree_root_cached`]
+    /// call rehashes only the affected path.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.data.len() {
+            return None;
+        }
+        if let Some(cache) = self.cache.as_mut() {
+            cache.mark_leaf_dirty(leaf_index_for::<T>(index));
+        }
+        Some(&mut self.data[index])
+    }
+}
+
+impl<T: ElementChunks, const N: usize> Index<usize> for Vector<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.data[index]
+    }
+}
+
+impl<T: ElementChunks, const N: usize> IndexMut<usize> for Vector<T, N> {
+    /// Index-assignment (`vector[i] = value`) goes through [`Vector::get_mut`], so it
+    /// dirties the element's chunk in the [`Cache`] just like any other mutating
+    /// accessor.
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+impl<T: SszSize, const N: usize> SszSize for Vector<T, N> {
+    const IS_VARIABLE_SIZE: bool = T::IS_VARIABLE_SIZE;
+
+    fn size_hint() -> usize {
+        N * T::size_hint()
+    }
+}
+
+impl<T: Serialize + SszSize, const N: usize> Serialize for Vector<T, N> {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<usize, SerializeError> {
+        serialize_collection(&self.data, buffer)
+    }
+}
+
+impl<T: Deserialize + SszSize, const N: usize> Deserialize for Vector<T, N> {
+    fn deserialize(encoding: &[u8]) -> Result<Self, DeserializeError> {
+        let data = deserialize_collection(encoding, Some(N))?;
+        Ok(Self { data, cache: None })
+    }
+}
+
+impl<T: ElementChunks, const N: usize> Composite for Vector<T, N> {}
+
+impl<T: ElementChunks, const N: usize> HashTreeRoot for Vector<T, N> {
+    fn hash_tree_root(&self) -> Result<Node, MerkleizationError> {
+        merkleize_elements(&self.data, chunk_count::<T>(N))
+    }
+}
+
+impl<T: ElementChunks, const N: usize> CachedHashTreeRoot for Vector<T, N> {
+    fn enable_cache(&mut self) {
+        self.cache = Some(Cache::new(chunk_count::<T>(N), false));
+    }
+
+    fn disable_cache(&mut self) {
+        self.cache = None;
+    }
+
+    fn hash_tree_root_cached(&mut self) -> Result<Node, MerkleizationError> {
+        let Some(cache) = self.cache.as_mut() else {
+            return self.hash_tree_root();
+        };
+        let data = &self.data;
+        Ok(cache.root(|leaf_index| leaf_bytes(data, leaf_index), None::<fn(&Node) -> Node>))
+    }
+}
+
+impl<T: ElementChunks, const N: usize> GeneralizedIndexable for Vector<T, N> {
+    fn chunk_count() -> usize {
+        chunk_count::<T>(N)
+    }
+
+    fn element_index(i: usize) -> GeneralizedIndex {
+        let leaf_count = Self::chunk_count().next_power_of_two().max(1);
+        GeneralizedIndex((leaf_count + leaf_index_for::<T>(i)) as u64)
+    }
+}
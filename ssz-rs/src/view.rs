@@ -0,0 +1,162 @@
+//! Zero-copy, borrowing deserialization.
+//!
+//! The default `Deserialize` path allocates an owned `List`/`Vector`/byte buffer for
+//! every value it reads, even when the caller immediately hashes or re-serializes it
+//! and never mutates it -- the common shape for large collections such as the
+//! transactions tree exercised in `bench_merkleization`. [`DeserializeView`] offers an
+//! alternative entry point, [`deserialize_borrowed`], that returns a view pointing
+//! directly into the input buffer instead of copying it into a new allocation.
+//!
+//! Owned `T` remains the default and the only type the rest of the crate's APIs
+//! require; borrowing is opt-in by calling [`deserialize_borrowed`] explicitly. A
+//! `SimpleSerialize` derive would generate a `View<'a>` for each derived container this
+//! same way: fixed-width scalar fields copied (they're cheap and borrowing them buys
+//! nothing), and byte-bearing fields -- a fixed `Vector<u8, N>` or variable-length
+//! `List<u8, N>` -- as slices bounded by the offsets resolved while reading the
+//! container's variable part, with no intermediate `Vec` allocation along the way. That
+//! derive doesn't exist yet (TODO); for now, the views below are hand-written:
+//! `ListView`/`VectorView` for the two byte-collection types, and `ListElementsView`
+//! for a `List<T, N>` whose elements have views of their own.
+
+use crate::{
+    de::{read_offset, DeserializeError},
+    ser::BYTES_PER_LENGTH_OFFSET,
+};
+use std::ops::Deref;
+
+/// Implemented by types that support a borrowed view over their SSZ encoding.
+pub trait DeserializeView {
+    /// The borrowed view type, parameterized by the lifetime of the input buffer.
+    type View<'a>
+    where
+        Self: 'a;
+
+    /// Parse `data` and return a view into it, validating bounds/length but copying no
+    /// byte content.
+    fn deserialize_borrowed(data: &[u8]) -> Result<Self::View<'_>, DeserializeError>;
+}
+
+/// Parse `data` as `T` and return a view borrowing from it rather than an owned `T`.
+pub fn deserialize_borrowed<T: DeserializeView>(
+    data: &[u8],
+) -> Result<T::View<'_>, DeserializeError> {
+    T::deserialize_borrowed(data)
+}
+
+/// A borrowed view of a variable-length byte `List<u8, N>`: the slice of `data` that
+/// was read, with no copy and no padding beyond what the caller wrote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListView<'a, const N: usize> {
+    data: &'a [u8],
+}
+
+impl<'a, const N: usize> ListView<'a, N> {
+    /// Borrow the underlying bytes for the duration of the input buffer's lifetime.
+    pub fn as_slice(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+impl<const N: usize> Deref for ListView<'_, N> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.data
+    }
+}
+
+impl<const N: usize> DeserializeView for crate::List<u8, N> {
+    type View<'a> = ListView<'a, N>;
+
+    fn deserialize_borrowed(data: &[u8]) -> Result<Self::View<'_>, DeserializeError> {
+        if data.len() > N {
+            return Err(DeserializeError::CollectionTooLarge { len: data.len(), bound: N });
+        }
+        Ok(ListView { data })
+    }
+}
+
+/// A borrowed view of a fixed-size byte `Vector<u8, N>`: always exactly `N` bytes,
+/// borrowed directly from the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VectorView<'a, const N: usize> {
+    data: &'a [u8],
+}
+
+impl<'a, const N: usize> VectorView<'a, N> {
+    /// Borrow the underlying bytes for the duration of the input buffer's lifetime.
+    pub fn as_slice(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+impl<const N: usize> Deref for VectorView<'_, N> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.data
+    }
+}
+
+impl<const N: usize> DeserializeView for crate::Vector<u8, N> {
+    type View<'a> = VectorView<'a, N>;
+
+    fn deserialize_borrowed(data: &[u8]) -> Result<Self::View<'_>, DeserializeError> {
+        if data.len() != N {
+            return Err(DeserializeError::InvalidByteLength(data.len()));
+        }
+        Ok(VectorView { data })
+    }
+}
+
+/// A borrowed view of a variable-length `List<T, N>` of composite (themselves
+/// borrowing) elements, such as the `List<List<u8, M>, 1048576>` transaction tree
+/// shape -- each element is parsed via `T::deserialize_borrowed`, so no element's byte
+/// content is ever copied; only the outer `Vec` of resolved views is allocated.
+#[derive(Debug, Clone)]
+pub struct ListElementsView<'a, T: DeserializeView, const N: usize> {
+    elements: Vec<T::View<'a>>,
+}
+
+impl<'a, T: DeserializeView, const N: usize> Deref for ListElementsView<'a, T, N> {
+    type Target = [T::View<'a>];
+
+    fn deref(&self) -> &[T::View<'a>] {
+        &self.elements
+    }
+}
+
+impl<T: DeserializeView, const N: usize> DeserializeView for crate::List<T, N> {
+    type View<'a> = ListElementsView<'a, T, N> where T: 'a;
+
+    fn deserialize_borrowed(data: &[u8]) -> Result<Self::View<'_>, DeserializeError> {
+        if data.is_empty() {
+            return Ok(ListElementsView { elements: Vec::new() });
+        }
+
+        let first_offset = read_offset(data, 0)?;
+        if first_offset as usize % BYTES_PER_LENGTH_OFFSET != 0 {
+            return Err(DeserializeError::InvalidOffset);
+        }
+        let len = first_offset as usize / BYTES_PER_LENGTH_OFFSET;
+        if len > N {
+            return Err(DeserializeError::CollectionTooLarge { len, bound: N });
+        }
+
+        let mut offsets = Vec::with_capacity(len + 1);
+        for i in 0..len {
+            offsets.push(read_offset(data, i)?);
+        }
+        offsets.push(data.len() as u32);
+
+        let mut elements = Vec::with_capacity(len);
+        for window in offsets.windows(2) {
+            let (start, end) = (window[0] as usize, window[1] as usize);
+            if end < start || end > data.len() {
+                return Err(DeserializeError::InvalidOffset);
+            }
+            elements.push(T::deserialize_borrowed(&data[start..end])?);
+        }
+        Ok(ListElementsView { elements })
+    }
+}